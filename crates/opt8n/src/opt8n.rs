@@ -1,13 +1,20 @@
-use std::{fs, path::PathBuf, sync::Arc};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use alloy::{
-    primitives::B256,
+    eips::BlockNumberOrTag,
+    primitives::{Bytes, B256, U256},
+    rlp::Encodable,
     rpc::types::{
         anvil::Forking,
         trace::geth::{
             GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions, GethTrace,
             PreStateConfig, PreStateFrame,
         },
+        FeeHistory,
     },
 };
 use anvil::{
@@ -19,19 +26,51 @@ use anvil::{
     NodeConfig, NodeHandle,
 };
 use anvil_core::eth::transaction::{PendingTransaction, TypedTransaction};
+use cast::args::Cast as CastArgs;
 use clap::{CommandFactory, FromArgMatches, Parser};
-use color_eyre::eyre::Result;
-use futures::StreamExt;
+use color_eyre::eyre::{self, Result};
+use futures::{stream::FuturesOrdered, StreamExt, TryStreamExt};
 use op_test_vectors::execution::{ExecutionFixture, ExecutionReceipt, ExecutionResult};
 use serde::{Deserialize, Serialize};
 use tokio::io::{AsyncBufReadExt, BufReader};
 
+/// `cast` subcommands that hit the node's RPC endpoint, as opposed to pure
+/// local utilities like `cast keccak` or `cast abi-encode` that take no
+/// `--rpc-url` and would fail clap parsing if one were forced on them.
+const CAST_NETWORK_SUBCOMMANDS: &[&str] = &[
+    "send",
+    "call",
+    "rpc",
+    "balance",
+    "nonce",
+    "code",
+    "storage",
+    "receipt",
+    "tx",
+    "block",
+    "chain",
+    "chain-id",
+    "gas-price",
+    "base-fee",
+    "age",
+    "client",
+    "publish",
+    "estimate",
+    "logs",
+    "find-block",
+];
+
 pub struct Opt8n {
     pub eth_api: EthApi,
     pub node_handle: NodeHandle,
     pub execution_fixture: ExecutionFixture,
     pub fork: Forking,
     pub output_file: PathBuf,
+    /// EVM snapshot ids pushed by [ReplCommand::Snapshot], most recent last.
+    pub snapshots: Vec<U256>,
+    /// Name of the hardfork this node was configured with, used as the EEST
+    /// `network` field. `None` means anvil's own default chain spec.
+    hardfork_name: Option<String>,
 }
 
 impl Opt8n {
@@ -41,6 +80,10 @@ impl Opt8n {
         output_file: PathBuf,
     ) -> Self {
         let node_config = node_config.unwrap_or_default().with_optimism(true);
+        let hardfork_name = node_config
+            .hardfork
+            .clone()
+            .map(|hardfork| format!("{hardfork:?}"));
         let (eth_api, node_handle) = anvil::spawn(node_config).await;
 
         Self {
@@ -49,6 +92,8 @@ impl Opt8n {
             execution_fixture: ExecutionFixture::default(),
             fork: fork.unwrap_or_default(),
             output_file,
+            snapshots: Vec::new(),
+            hardfork_name,
         }
     }
 
@@ -93,7 +138,7 @@ impl Opt8n {
 
     async fn execute(&mut self, command: ReplCommand) -> Result<()> {
         match command {
-            ReplCommand::Dump => self.dump_execution_fixture().await?,
+            ReplCommand::Dump { format } => self.dump_execution_fixture(format).await?,
             ReplCommand::Anvil { mut args } => {
                 args.insert(0, "anvil".to_string());
                 let command = NodeArgs::command_for_update();
@@ -101,20 +146,122 @@ impl Opt8n {
                 let node_args = NodeArgs::from_arg_matches(&matches)?;
                 node_args.run().await?;
             }
-            ReplCommand::Cast { .. } => {}
+            ReplCommand::Cast { mut args } => {
+                let needs_rpc_url = args
+                    .first()
+                    .is_some_and(|subcommand| CAST_NETWORK_SUBCOMMANDS.contains(&subcommand.as_str()));
+                let has_rpc_url = args.iter().any(|arg| arg == "--rpc-url" || arg == "-r");
+
+                args.insert(0, "cast".to_string());
+                if needs_rpc_url && !has_rpc_url {
+                    args.push("--rpc-url".to_string());
+                    args.push(self.node_handle.http_endpoint());
+                }
+
+                let command = CastArgs::command_for_update();
+                let matches = command.try_get_matches_from(args)?;
+                let cast_args = CastArgs::from_arg_matches(&matches)?;
+                cast_args.run().await?;
+            }
+            ReplCommand::Clear => self.clear_fixture(),
+            ReplCommand::Reset => {
+                let _ = self.eth_api.backend.reset_fork(self.fork.clone()).await;
+                self.clear_fixture();
+                // `reset_fork` invalidates every outstanding snapshot id.
+                self.snapshots.clear();
+            }
+            ReplCommand::Snapshot => {
+                let snapshot_id = self.eth_api.evm_snapshot().await?;
+                println!("Snapshot: {snapshot_id}");
+                self.snapshots.push(snapshot_id);
+            }
+            ReplCommand::Revert => match self.snapshots.pop() {
+                Some(snapshot_id) => {
+                    if !self.eth_api.evm_revert(snapshot_id).await? {
+                        eyre::bail!("snapshot {snapshot_id} is no longer valid");
+                    }
+                }
+                None => eprintln!("Error: no snapshot to revert to"),
+            },
             ReplCommand::Exit => unreachable!(),
         }
         Ok(())
     }
 
+    /// Wipes the transactions and pre/post allocations collected so far,
+    /// without restarting anvil.
+    fn clear_fixture(&mut self) {
+        self.execution_fixture.transactions.clear();
+        self.execution_fixture.alloc.clear();
+        self.execution_fixture.out_alloc.clear();
+    }
+
+    /// Runs a forge script against this node's RPC endpoint and captures every
+    /// transaction it broadcasts into the execution fixture.
+    ///
+    /// This shells out to a `forge` binary that must be on `PATH`. Broadcasts
+    /// are picked up from both the blocks anvil mined while the script ran
+    /// (the instamining case) and anything still sitting in the mempool
+    /// afterwards (the interval/no-mine case), so this works either way.
+    pub async fn run_script(&mut self, script_path: &Path) -> Result<()> {
+        let rpc_url = self.node_handle.http_endpoint();
+        let start_block = self.eth_api.backend.best_number();
+
+        let status = tokio::process::Command::new("forge")
+            .arg("script")
+            .arg(script_path)
+            .arg("--rpc-url")
+            .arg(&rpc_url)
+            .arg("--broadcast")
+            .arg("--unlocked")
+            .status()
+            .await?;
+        if !status.success() {
+            eyre::bail!("forge script {} failed to run", script_path.display());
+        }
+
+        let end_block = self.eth_api.backend.best_number();
+        let mut mined_txs = vec![];
+        for number in (start_block + 1)..=end_block {
+            if let Some(block) = self.eth_api.backend.get_block(number) {
+                mined_txs.extend(
+                    block
+                        .transactions
+                        .into_iter()
+                        .map(|tx| tx.transaction)
+                        .collect::<Vec<_>>(),
+                );
+            }
+        }
+
+        let pending_txs = self
+            .eth_api
+            .pool
+            .ready_transactions()
+            .map(|tx| tx.pending_transaction.transaction.clone())
+            .collect::<Vec<_>>();
+
+        if mined_txs.is_empty() && pending_txs.is_empty() {
+            eyre::bail!(
+                "forge script {} broadcast no transactions onto the running node",
+                script_path.display()
+            );
+        }
+
+        self.execution_fixture.transactions.extend(mined_txs);
+        self.execution_fixture.transactions.extend(pending_txs);
+
+        Ok(())
+    }
+
     /// Updates the pre and post state allocations of the [ExecutionFixture].
     pub async fn update_alloc(&mut self, transactions: &Vec<TypedTransaction>) -> Result<()> {
-        // TODO: Make this concurrent
-        for transaction in transactions {
-            if let GethTrace::PreStateTracer(PreStateFrame::Diff(frame)) = self
-                .eth_api
-                .backend
-                .debug_trace_transaction(
+        // Launch every trace concurrently, but keep them keyed by transaction
+        // index so the merge below stays byte-identical to the serial version.
+        let traces = transactions
+            .iter()
+            .map(|transaction| {
+                self.eth_api.backend.debug_trace_transaction(
                     transaction.hash(),
                     GethDebugTracingOptions {
                         tracer: Some(GethDebugTracerType::BuiltInTracer(
@@ -126,8 +273,16 @@ impl Opt8n {
                         diff_mode: Some(true),
                     }),
                 )
-                .await?
-            {
+            })
+            .collect::<FuturesOrdered<_>>()
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        // Merge sequentially in original transaction order: pre-state keeps
+        // the first writer (earliest tx), post-state keeps the last writer
+        // (latest tx). Merging out of order would corrupt `out_alloc`.
+        for trace in traces {
+            if let GethTrace::PreStateTracer(PreStateFrame::Diff(frame)) = trace {
                 frame.pre.into_iter().for_each(|(address, account)| {
                     self.execution_fixture
                         .alloc
@@ -144,7 +299,7 @@ impl Opt8n {
         Ok(())
     }
 
-    pub async fn dump_execution_fixture(&mut self) -> Result<()> {
+    pub async fn dump_execution_fixture(&mut self, format: DumpFormat) -> Result<()> {
         // Reset the fork
         let _ = self.eth_api.backend.reset_fork(self.fork.clone()).await;
         let pool_txs = self
@@ -165,6 +320,19 @@ impl Opt8n {
             .collect::<Vec<Arc<_>>>();
 
         let mined_block = self.eth_api.backend.mine_block(pool_txs).await;
+
+        // Base fee / gas usage context for the mined block, so downstream
+        // consumers can assert a client computes the next base fee correctly.
+        let fee_history = self
+            .eth_api
+            .fee_history(
+                U256::from(1),
+                BlockNumberOrTag::Number(mined_block.block_number),
+                Some(vec![10.0, 50.0, 90.0]),
+            )
+            .await?;
+
+        let mut blockchain_test = None;
         if let Some(block) = self.eth_api.backend.get_block(mined_block.block_number) {
             // TODO: collect into futures ordered
             let mut receipts: Vec<ExecutionReceipt> = vec![];
@@ -195,16 +363,103 @@ impl Opt8n {
                 receipts,
             };
 
+            if format == DumpFormat::Eest {
+                let header_to_eest = |header: &alloy::consensus::Header| eest::EestBlockHeader {
+                    parent_hash: header.parent_hash,
+                    ommers_hash: header.ommers_hash,
+                    coinbase: header.beneficiary,
+                    state_root: header.state_root,
+                    transactions_root: header.transactions_root,
+                    receipts_root: header.receipts_root,
+                    bloom: header.logs_bloom,
+                    difficulty: header.difficulty,
+                    number: U256::from(header.number),
+                    gas_limit: U256::from(header.gas_limit),
+                    gas_used: U256::from(header.gas_used),
+                    timestamp: U256::from(header.timestamp),
+                    extra_data: header.extra_data.clone(),
+                    mix_hash: header.mix_hash,
+                    nonce: header.nonce,
+                    base_fee_per_gas: header.base_fee_per_gas.map(U256::from),
+                    withdrawals_root: header.withdrawals_root,
+                };
+
+                let header = header_to_eest(&block.header);
+                // Encode the whole block (header + transactions + ommers), not
+                // just the header, so the bytes are a decodable, replayable
+                // block rather than a header-only fragment.
+                let mut rlp = Vec::new();
+                block.encode(&mut rlp);
+
+                let genesis_block_header = self
+                    .eth_api
+                    .backend
+                    .get_block(0)
+                    .map(|genesis| header_to_eest(&genesis.header))
+                    .unwrap_or_else(|| header.clone());
+
+                blockchain_test = Some(eest::BlockchainTest {
+                    network: self.fork_network_name(),
+                    pre: self
+                        .execution_fixture
+                        .alloc
+                        .iter()
+                        .map(|(address, account)| (*address, account.clone().into()))
+                        .collect(),
+                    genesis_block_header,
+                    blocks: vec![eest::EestBlock {
+                        header: eest::EestBlockHeader {
+                            state_root: execution_result.state_root,
+                            transactions_root: execution_result.tx_root,
+                            receipts_root: execution_result.receipt_root,
+                            bloom: execution_result.logs_bloom,
+                            ..header
+                        },
+                        rlp: Bytes::from(rlp),
+                    }],
+                    post_state: self
+                        .execution_fixture
+                        .out_alloc
+                        .iter()
+                        .map(|(address, account)| (*address, account.clone().into()))
+                        .collect(),
+                    // Surfaced here too so conformance consumers replaying
+                    // this exact format can assert base-fee transitions.
+                    fee_history: fee_history.clone(),
+                });
+            }
+
             self.execution_fixture.env = block.into();
             self.execution_fixture.result = execution_result;
         }
 
-        // Output the execution fixture to file
+        // Output the fixture to file in the requested format.
         let file = fs::File::create(&self.output_file)?;
-        serde_json::to_writer_pretty(file, &self.execution_fixture)?;
+        match blockchain_test {
+            Some(blockchain_test) => serde_json::to_writer_pretty(file, &blockchain_test)?,
+            // `ExecutionFixture` is defined upstream, so its fee history is
+            // surfaced by flattening it alongside a sibling field rather than
+            // by editing the foreign type.
+            None => serde_json::to_writer_pretty(
+                file,
+                &NativeFixture {
+                    fixture: &self.execution_fixture,
+                    fee_history,
+                },
+            )?,
+        }
 
         Ok(())
     }
+
+    /// Name of the active hardfork/chain spec for the EEST `network` field:
+    /// the configured hardfork (e.g. `Cancun`, an OP hardfork), or `Optimism`
+    /// when none was configured. It is never the fork *source* RPC URL.
+    fn fork_network_name(&self) -> String {
+        self.hardfork_name
+            .clone()
+            .unwrap_or_else(|| "Optimism".to_string())
+    }
 }
 
 #[derive(Parser, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -220,13 +475,136 @@ pub enum ReplCommand {
         #[arg(index = 1, allow_hyphen_values = true)]
         args: Vec<String>,
     },
-    Dump,
-    // TODO: implement clear
-    // TODO: implement reset
+    Dump {
+        #[arg(value_enum, default_value_t = DumpFormat::Native)]
+        format: DumpFormat,
+    },
+    /// Wipes the collected transactions and pre/post allocations, without
+    /// restarting anvil.
+    Clear,
+    /// Resets the fork back to its starting point and clears the fixture, so
+    /// a fresh scenario can be authored against the same node.
+    Reset,
+    /// Takes an EVM snapshot that a later `revert` can roll back to.
+    Snapshot,
+    /// Reverts to the most recent EVM snapshot taken with `snapshot`.
+    Revert,
     #[command(visible_alias = "e")]
     Exit,
 }
 
+/// The native [DumpFormat::Native] output: the upstream `ExecutionFixture`
+/// with the mined block's fee history flattened in alongside it.
+#[derive(Serialize)]
+struct NativeFixture<'a> {
+    #[serde(flatten)]
+    fixture: &'a ExecutionFixture,
+    fee_history: FeeHistory,
+}
+
+/// The output format written by [Opt8n::dump_execution_fixture].
+#[derive(Clone, Copy, Debug, Default, clap::ValueEnum, Serialize, Deserialize, PartialEq, Eq)]
+#[clap(rename_all = "kebab_case")]
+pub enum DumpFormat {
+    /// The crate's bespoke [ExecutionFixture] format.
+    #[default]
+    Native,
+    /// An Ethereum execution-spec-tests (EEST/retesteth) "blockchain test",
+    /// consumable by hive simulators and existing EL conformance tooling.
+    Eest,
+}
+
+/// Types making up the [DumpFormat::Eest] "blockchain test" output.
+pub mod eest {
+    use std::collections::BTreeMap;
+
+    use alloy::{
+        primitives::{Address, Bloom, Bytes, B256, B64, U256},
+        rpc::types::{trace::geth::AccountState, FeeHistory},
+    };
+    use serde::{Deserialize, Serialize};
+
+    /// A single account entry in the `pre`/`postState` sections.
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+    pub struct EestAccount {
+        pub balance: U256,
+        pub nonce: U256,
+        pub code: Bytes,
+        pub storage: BTreeMap<B256, B256>,
+    }
+
+    impl From<AccountState> for EestAccount {
+        fn from(account: AccountState) -> Self {
+            Self {
+                balance: account.balance.unwrap_or_default(),
+                nonce: U256::from(account.nonce.unwrap_or_default()),
+                code: account.code.unwrap_or_default(),
+                storage: account.storage.unwrap_or_default(),
+            }
+        }
+    }
+
+    /// Header fields shared by `genesisBlockHeader` and each entry of
+    /// `blocks`, named to match the upstream EEST/retesteth blockchain-test
+    /// schema rather than this crate's own conventions.
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+    pub struct EestBlockHeader {
+        #[serde(rename = "parentHash")]
+        pub parent_hash: B256,
+        #[serde(rename = "uncleHash")]
+        pub ommers_hash: B256,
+        pub coinbase: Address,
+        #[serde(rename = "stateRoot")]
+        pub state_root: B256,
+        #[serde(rename = "transactionsTrie")]
+        pub transactions_root: B256,
+        #[serde(rename = "receiptTrie")]
+        pub receipts_root: B256,
+        pub bloom: Bloom,
+        pub difficulty: U256,
+        pub number: U256,
+        #[serde(rename = "gasLimit")]
+        pub gas_limit: U256,
+        #[serde(rename = "gasUsed")]
+        pub gas_used: U256,
+        pub timestamp: U256,
+        #[serde(rename = "extraData")]
+        pub extra_data: Bytes,
+        #[serde(rename = "mixHash")]
+        pub mix_hash: B256,
+        pub nonce: B64,
+        #[serde(rename = "baseFeePerGas", skip_serializing_if = "Option::is_none")]
+        pub base_fee_per_gas: Option<U256>,
+        #[serde(rename = "withdrawalsRoot", skip_serializing_if = "Option::is_none")]
+        pub withdrawals_root: Option<B256>,
+    }
+
+    /// One mined block: its header plus the RLP-encoded block a client replays
+    /// to validate the vector against its own execution.
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct EestBlock {
+        #[serde(flatten)]
+        pub header: EestBlockHeader,
+        pub rlp: Bytes,
+    }
+
+    /// An Ethereum execution-spec-tests (EEST/retesteth) "blockchain test".
+    // `FeeHistory::gas_used_ratio` is `Vec<f64>`, so this can't derive `Eq`.
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BlockchainTest {
+        pub network: String,
+        pub pre: BTreeMap<Address, EestAccount>,
+        pub genesis_block_header: EestBlockHeader,
+        pub blocks: Vec<EestBlock>,
+        pub post_state: BTreeMap<Address, EestAccount>,
+        /// Base-fee/gas-usage context for the mined block, so conformance
+        /// consumers replaying this format can assert base-fee transitions.
+        pub fee_history: FeeHistory,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[tokio::test]
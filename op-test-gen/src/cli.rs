@@ -1,6 +1,7 @@
 use anvil::cmd::NodeArgs;
 use clap::{Parser, Subcommand};
 use color_eyre::eyre;
+use opt8n::{DumpFormat, Opt8n};
 use std::fmt::Debug;
 use std::path::PathBuf;
 
@@ -17,10 +18,14 @@ pub enum Commands {
     /// Uses a forge script to generate a test vector
     #[command(visible_alias = "s")]
     Script {
-        /// Path to the forge script
-        #[arg(long, use_value_delimiter = true, value_delimiter = ',')]
+        /// Path to the forge script(s)
+        #[arg(required = true)]
         path: Vec<PathBuf>,
 
+        /// Where the resulting execution fixture is written
+        #[arg(short, long, default_value = "fixture.json")]
+        output: PathBuf,
+
         #[command(flatten)]
         node_args: NodeArgs,
     },
@@ -29,9 +34,24 @@ pub enum Commands {
 impl Cli {
     pub async fn run(&self) -> eyre::Result<()> {
         match &self.command {
-            Commands::Script { path, node_args } => {
-                println!("Running scripts: {:?}", path);
-                node_args.clone().run().await?;
+            Commands::Script {
+                path,
+                output,
+                node_args,
+            } => {
+                let mut opt8n = Opt8n::new(
+                    Some(node_args.clone().into_node_config()),
+                    None,
+                    output.clone(),
+                )
+                .await;
+
+                for script_path in path {
+                    println!("Running script: {}", script_path.display());
+                    opt8n.run_script(script_path).await?;
+                }
+
+                opt8n.dump_execution_fixture(DumpFormat::Native).await?;
                 Ok(())
             }
         }